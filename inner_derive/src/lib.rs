@@ -0,0 +1,289 @@
+//! `#[derive(Inner)]` - companion proc-macro for the `inner` crate.
+//!
+//! Hand-writing `IntoResult` and the matching `is_*`/`into_*`/`as_*`
+//! accessors for every enum gets old fast. This derive generates them for
+//! you, so `inner!` (and the plain accessors) work immediately:
+//!
+//! ```ignore
+//! #[macro_use]
+//! extern crate inner;
+//! use inner_derive::Inner;
+//!
+//! #[derive(Inner, Debug, PartialEq)]
+//! #[inner(ok(Apple, Orange))]
+//! enum Fruit {
+//!     Apple(i32),
+//!     Orange(i16),
+//!     Rotten,
+//! }
+//!
+//! let z = Fruit::Apple(15);
+//! assert_eq!(z.into_apple(), Ok(15));
+//! assert!(Fruit::Rotten.is_rotten());
+//! assert_eq!(9, inner!(Fruit::Apple(9)));
+//! ```
+//!
+//! For every variant, `#[derive(Inner)]` generates:
+//!
+//! - `pub fn is_variant(&self) -> bool`
+//! - `pub fn into_variant(self) -> Result<T, Self>` (tuple/struct variants only)
+//! - `pub fn as_variant(&self) -> Result<&T, &Self>` (tuple/struct variants only)
+//! - `pub fn as_variant_mut(&mut self) -> Result<&mut T, &mut Self>` (tuple/struct variants only)
+//!
+//! where `T` is the single field's type, or a tuple of the fields' types
+//! (in declaration order) for multi-field tuple and struct variants. For
+//! multi-field variants, `as_variant` and `as_variant_mut` return a tuple
+//! of references to the fields (`(&A, &B)`, `(&mut A, &mut B)`) rather
+//! than a reference to a tuple.
+//!
+//! The optional `#[inner(ok(Variant1, Variant2, ...))]` attribute on the
+//! enum additionally generates an `IntoResult<T, Self>` impl that treats
+//! the listed variants as `Ok` and everything else as `Err(self)`. All
+//! listed variants must carry exactly one field. `T` is the payload type
+//! of whichever listed variant is declared *first in the enum* (not
+//! whichever is named first in the attribute); payloads of the other
+//! listed variants are `as`-cast into it, just like the hand-written
+//! `IntoResult` impl in the main crate's docs casts its `Orange(i16)`
+//! into an `i32`. As with any `as` cast, if a listed variant's payload is
+//! wider than `T`, the cast is lossy - order the enum's variants (widest
+//! payload first) if that matters for your types.
+//!
+//! # License
+//! Apache2.0/MIT
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, Ident, Meta, NestedMeta, Type, Variant,
+};
+
+#[proc_macro_derive(Inner, attributes(inner))]
+pub fn derive_inner(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => panic!("#[derive(Inner)] only supports enums"),
+    };
+
+    let accessors = variants.iter().map(|v| accessors_for(name, v));
+
+    let ok_variants = ok_variants_from_attrs(&input.attrs);
+    let into_result_impl = ok_variants.map(|ok_variants| {
+        into_result_impl_for(name, variants, &ok_variants)
+    });
+
+    let expanded = quote! {
+        impl #name {
+            #(#accessors)*
+        }
+
+        #into_result_impl
+    };
+    expanded.into()
+}
+
+/// Generates `is_*`/`into_*`/`as_*` for a single variant.
+fn accessors_for(name: &Ident, variant: &Variant) -> proc_macro2::TokenStream {
+    let vname = &variant.ident;
+    let snake = to_snake_case(&vname.to_string());
+    let is_fn = Ident::new(&format!("is_{}", snake), Span::call_site());
+
+    let is_impl = quote! {
+        pub fn #is_fn(&self) -> bool {
+            match self {
+                #name::#vname { .. } => true,
+                _ => false,
+            }
+        }
+    };
+
+    let (pattern, types, value) = match &variant.fields {
+        Fields::Unit => return is_impl,
+        Fields::Unnamed(fields) => {
+            let binders: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|i| Ident::new(&format!("f{}", i), Span::call_site()))
+                .collect();
+            let types: Vec<&Type> = fields.unnamed.iter().map(|f| &f.ty).collect();
+            (quote! { (#(#binders),*) }, types, binders)
+        }
+        Fields::Named(fields) => {
+            let binders: Vec<Ident> = fields
+                .named
+                .iter()
+                .map(|f| f.ident.clone().unwrap())
+                .collect();
+            let types: Vec<&Type> = fields.named.iter().map(|f| &f.ty).collect();
+            (quote! { { #(#binders),* } }, types, binders)
+        }
+    };
+
+    let into_fn = Ident::new(&format!("into_{}", snake), Span::call_site());
+    let as_fn = Ident::new(&format!("as_{}", snake), Span::call_site());
+    let as_mut_fn = Ident::new(&format!("as_{}_mut", snake), Span::call_site());
+    let into_ty = tuple_of(&types);
+    let as_ty = tuple_of(&types.iter().map(|ty| quote! { &#ty }).collect::<Vec<_>>());
+    let as_mut_ty = tuple_of(&types.iter().map(|ty| quote! { &mut #ty }).collect::<Vec<_>>());
+    let value = if value.len() == 1 {
+        quote! { #(#value)* }
+    } else {
+        quote! { (#(#value),*) }
+    };
+
+    quote! {
+        #is_impl
+
+        pub fn #into_fn(self) -> Result<#into_ty, Self> {
+            match self {
+                #name::#vname #pattern => Ok(#value),
+                other => Err(other),
+            }
+        }
+
+        pub fn #as_fn(&self) -> Result<#as_ty, &Self> {
+            match self {
+                #name::#vname #pattern => Ok(#value),
+                other => Err(other),
+            }
+        }
+
+        pub fn #as_mut_fn(&mut self) -> Result<#as_mut_ty, &mut Self> {
+            match self {
+                #name::#vname #pattern => Ok(#value),
+                other => Err(other),
+            }
+        }
+    }
+}
+
+/// Wraps field types in a tuple, unless there is exactly one.
+fn tuple_of<T: quote::ToTokens>(types: &[T]) -> proc_macro2::TokenStream {
+    if types.len() == 1 {
+        quote! { #(#types)* }
+    } else {
+        quote! { (#(#types),*) }
+    }
+}
+
+/// Reads the variant names out of `#[inner(ok(A, B, ...))]`, if present.
+fn ok_variants_from_attrs(attrs: &[syn::Attribute]) -> Option<Vec<Ident>> {
+    for attr in attrs {
+        if !attr.path.is_ident("inner") {
+            continue;
+        }
+        let meta = attr.parse_meta().expect("malformed #[inner(..)] attribute");
+        if let Meta::List(list) = meta {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::List(ok_list)) = nested {
+                    if ok_list.path.is_ident("ok") {
+                        return Some(
+                            ok_list
+                                .nested
+                                .iter()
+                                .map(|n| match n {
+                                    NestedMeta::Meta(Meta::Path(p)) => {
+                                        p.get_ident().expect("expected a variant name").clone()
+                                    }
+                                    _ => panic!("expected a variant name in #[inner(ok(..))]"),
+                                })
+                                .collect(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Builds `impl IntoResult<T, Self> for Enum`, treating `ok_variants` as `Ok`.
+///
+/// `T` is the payload type of whichever listed variant is declared
+/// *first in the enum* - not whichever is named first in
+/// `#[inner(ok(..))]`, so the generated impl doesn't depend on the order
+/// the attribute happens to list variants in. Payloads of the other
+/// listed variants are `as`-cast into `T`, mirroring how a hand-written
+/// `IntoResult` impl would coerce e.g. an `i16` variant into an `i32`
+/// result; as with any `as` cast, casting into a narrower type is lossy.
+fn into_result_impl_for(
+    name: &Ident,
+    variants: &syn::punctuated::Punctuated<Variant, syn::token::Comma>,
+    ok_variants: &[Ident],
+) -> proc_macro2::TokenStream {
+    for v in ok_variants {
+        if !variants.iter().any(|candidate| &candidate.ident == v) {
+            panic!("#[inner(ok(..))] names unknown variant `{}`", v);
+        }
+    }
+
+    let mut ok_ty: Option<Type> = None;
+    let mut arms = Vec::new();
+
+    for variant in variants {
+        let vname = &variant.ident;
+        if !ok_variants.contains(vname) {
+            continue;
+        }
+        let field = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => panic!(
+                "#[inner(ok(..))] only supports single-field tuple variants, `{}` isn't one",
+                vname
+            ),
+        };
+        let ok_ty = ok_ty.get_or_insert_with(|| field.clone());
+        let value = if *field == *ok_ty {
+            quote! { q }
+        } else {
+            quote! { q as #ok_ty }
+        };
+        arms.push(quote! { #name::#vname(q) => Ok(#value), });
+    }
+
+    let ok_ty = ok_ty.expect("#[inner(ok(..))] needs at least one variant");
+
+    quote! {
+        impl ::inner::IntoResult<#ok_ty, #name> for #name {
+            fn into_result(self) -> Result<#ok_ty, #name> {
+                match self {
+                    #(#arms)*
+                    other => Err(other),
+                }
+            }
+        }
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.char_indices() {
+        if c.is_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_snake_case;
+
+    #[test]
+    fn snake_case_single_word() {
+        assert_eq!(to_snake_case("Apple"), "apple");
+    }
+
+    #[test]
+    fn snake_case_multi_word() {
+        assert_eq!(to_snake_case("RottenApple"), "rotten_apple");
+    }
+}
@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate inner;
+
+use inner_derive::Inner;
+
+#[derive(Inner, Debug, PartialEq)]
+enum Fruit {
+    Apple(i32),
+    #[allow(dead_code)]
+    Orange(i16),
+    Rotten,
+}
+
+#[test]
+fn unit_variant() {
+    assert!(Fruit::Rotten.is_rotten());
+    assert!(!Fruit::Apple(1).is_rotten());
+}
+
+#[test]
+fn tuple_variant() {
+    let z = Fruit::Apple(15);
+    assert!(z.is_apple());
+    assert_eq!(z.into_apple(), Ok(15));
+
+    let z = Fruit::Rotten;
+    assert_eq!(z.into_apple(), Err(Fruit::Rotten));
+}
+
+#[test]
+fn tuple_variant_as_ref() {
+    let z = Fruit::Apple(15);
+    assert_eq!(z.as_apple(), Ok(&15));
+}
+
+#[test]
+fn tuple_variant_as_mut() {
+    let mut z = Fruit::Apple(15);
+    *z.as_apple_mut().unwrap() += 1;
+    assert_eq!(z.as_apple(), Ok(&16));
+}
+
+#[derive(Inner, Debug, PartialEq)]
+enum Shape {
+    Rect(u32, u32),
+    #[allow(dead_code)]
+    Circle(u32),
+}
+
+#[test]
+fn multi_field_tuple_variant() {
+    let z = Shape::Rect(3, 4);
+    assert_eq!(z.into_rect(), Ok((3, 4)));
+}
+
+#[derive(Inner, Debug, PartialEq)]
+enum Msg {
+    Move { x: i32, y: i32 },
+    #[allow(dead_code)]
+    Quit,
+}
+
+#[test]
+fn struct_variant() {
+    let z = Msg::Move { x: 1, y: 2 };
+    assert_eq!(z.into_move(), Ok((1, 2)));
+    assert!(Msg::Quit.is_quit());
+}
+
+#[derive(Inner, Debug, PartialEq)]
+#[inner(ok(Apple, Orange))]
+enum OkFruit {
+    Apple(i32),
+    Orange(i16),
+    Rotten,
+}
+
+#[test]
+fn ok_attribute_generates_into_result() {
+    let z = OkFruit::Apple(15);
+    assert_eq!(15, inner!(z));
+
+    let z = OkFruit::Orange(9);
+    assert_eq!(9, inner!(z));
+
+    let z = OkFruit::Rotten;
+    let y = inner!(z, else |e| {
+        assert_eq!(e, OkFruit::Rotten);
+        0
+    });
+    assert_eq!(y, 0);
+}
+
+// Same enum as `OkFruit`, but `#[inner(ok(..))]` names its variants in
+// the opposite order. The generated `Ok` type still comes out as `i32`
+// (Apple's type, since Apple is declared first), not `i16` - the
+// attribute's argument order must not affect the result.
+#[derive(Inner, Debug, PartialEq)]
+#[inner(ok(Orange, Apple))]
+enum OkFruitReordered {
+    Apple(i32),
+    #[allow(dead_code)]
+    Orange(i16),
+    #[allow(dead_code)]
+    Rotten,
+}
+
+#[test]
+fn ok_attribute_is_order_independent() {
+    let z = OkFruitReordered::Apple(100_000);
+    assert_eq!(100_000, inner!(z));
+}
+
+// Regression test: the generated accessors must be callable from outside
+// the defining module, not just from `#[test]`s that happen to share it.
+mod veggies {
+    use inner_derive::Inner;
+
+    #[derive(Inner, Debug, PartialEq)]
+    pub enum Veggie {
+        Carrot(i32),
+        #[allow(dead_code)]
+        Rotten,
+    }
+}
+
+#[test]
+fn accessors_are_public_across_modules() {
+    let z = veggies::Veggie::Carrot(7);
+    assert!(z.is_carrot());
+    assert_eq!(z.as_carrot(), Ok(&7));
+
+    let mut z = veggies::Veggie::Carrot(7);
+    *z.as_carrot_mut().unwrap() += 1;
+    assert_eq!(z.into_carrot(), Ok(8));
+}
@@ -105,6 +105,159 @@
 //! # }
 //! ```
 //!
+//! # Multi-field tuple and struct variants
+//! Name the fields you want right in the `if` clause, and they come back
+//! as a tuple, in the order you named them:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn main() {
+//! enum Shape {
+//!     Rect(u32, u32),
+//!     Circle(u32),
+//! }
+//!
+//! let z = Shape::Rect(3, 4);
+//! let (w, h) = inner!(z, if Shape::Rect(w, h));
+//! assert_eq!(w * h, 12);
+//! # }
+//! ```
+//!
+//! Struct variants work the same way, with `{ }` instead of `( )`:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn main() {
+//! enum Msg {
+//!     Move { x: i32, y: i32 },
+//!     Quit,
+//! }
+//!
+//! let z = Msg::Move { x: 1, y: 2 };
+//! let (x, y) = inner!(z, if Msg::Move { x, y }, else { (0, 0) });
+//! assert_eq!((x, y), (1, 2));
+//! # }
+//! ```
+//!
+//! Both forms support the same `else` and `else |e|` clauses as the
+//! single-field case above.
+//!
+//! The enum path isn't limited to `Type::Variant` - any number of
+//! module segments works too:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn main() {
+//! mod shapes {
+//!     pub enum Shape {
+//!         Rect(u32, u32),
+//!         Circle(u32),
+//!     }
+//! }
+//!
+//! let z = shapes::Shape::Rect(3, 4);
+//! let (w, h) = inner!(z, if shapes::Shape::Rect(w, h));
+//! assert_eq!(w * h, 12);
+//! # }
+//! ```
+//!
+//! # Several variants at once
+//! If more than one variant should be accepted, list them separated by
+//! `|`. They must all carry the same payload type:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn main() {
+//! enum Fruit {
+//!     Apple(i32),
+//!     Pear(i32),
+//!     Rotten,
+//! }
+//!
+//! let z = Fruit::Pear(7);
+//! let y = inner!(z, if Fruit::Apple | Fruit::Pear, else { 0 });
+//! assert_eq!(y, 7);
+//!
+//! let z = Fruit::Rotten;
+//! let y = inner!(z, if Fruit::Apple | Fruit::Pear, else { 0 });
+//! assert_eq!(y, 0);
+//! # }
+//! ```
+//!
+//! # Borrowing instead of moving
+//! `inner!` matches its argument by value, so you can't use it on
+//! something you only have a `&T`/`&mut T` to without cloning it first.
+//! `inner_ref!` and `inner_mut!` solve that by borrowing instead, and
+//! support the same `if Variant`, `else` and `else |e|` forms:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn main() {
+//! enum Fruit {
+//!     Apple(i32),
+//!     Orange(i16),
+//! }
+//!
+//! let mut z = Fruit::Apple(15);
+//! assert_eq!(*inner_ref!(z, if Fruit::Apple), 15);
+//!
+//! *inner_mut!(z, if Fruit::Apple) += 1;
+//! assert_eq!(*inner_ref!(z, if Fruit::Apple), 16);
+//! # }
+//! ```
+//!
+//! # Propagating instead of panicking
+//! Sometimes you don't want to panic or write an `else` block at all -
+//! you just want a `Result` you can use with `?`. `try_inner!` gives you
+//! that:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn double_it() -> Result<i32, ()> {
+//! let x = Some(3);
+//! let v = try_inner!(x)?;
+//! Ok(v * 2)
+//! # }
+//! # fn main() {
+//! assert_eq!(double_it(), Ok(6));
+//! # }
+//! ```
+//!
+//! With an `if` clause, `try_inner!(x, if Variant)` gives back the
+//! mismatched value itself in the `Err` arm:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # fn main() {
+//! enum Fruit {
+//!     Apple(i32),
+//!     Orange(i16),
+//! }
+//!
+//! let z = Fruit::Orange(15);
+//! assert!(try_inner!(z, if Fruit::Apple).is_err());
+//! # }
+//! ```
+//!
+//! Add an `err` clause to map the mismatch into your own error type
+//! before it reaches `?`:
+//!
+//! ```
+//! # #[macro_use] extern crate inner;
+//! # #[derive(Debug, PartialEq)]
+//! # enum MyError { Wrong }
+//! # fn main() {
+//! enum Fruit {
+//!     Apple(i32),
+//!     Orange(i16),
+//! }
+//!
+//! let z = Fruit::Orange(15);
+//! let y: Result<i32, MyError> = try_inner!(z, if Fruit::Apple, err MyError::Wrong);
+//! assert_eq!(y, Err(MyError::Wrong));
+//! # }
+//! ```
+//!
 //! Another option is to implement this crate's `IntoResult` trait for
 //! your enum. Then you don't have to write an `if` clause to tell what
 //! enum variant you want to descend into, and you can choose more than
@@ -161,11 +314,65 @@ impl<T> IntoResult<T, ()> for Option<T> {
 /// The `inner!` macro - see module level documentation for details.
 #[macro_export]
 macro_rules! inner {
+    ($x:expr, if $($p:ident)::+ ( $($q:ident),+ ), else |$e:ident| $b:block) => {
+        {
+            match $x {
+                $($p)::+($($q),+) => ($($q),+),
+                $e => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $($p:ident)::+ ( $($q:ident),+ ), else $b:block) => {
+        {
+            match $x {
+                $($p)::+($($q),+) => ($($q),+),
+                _ => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $($p:ident)::+ ( $($q:ident),+ )) => {
+        {
+            match $x {
+                $($p)::+($($q),+) => ($($q),+),
+                _ => panic!("Unexpected value found inside '{}'", stringify!($x)),
+            }
+        }
+    };
+
+    ($x:expr, if $($p:ident)::+ { $($q:ident),+ }, else |$e:ident| $b:block) => {
+        {
+            match $x {
+                $($p)::+ { $($q),+ } => ($($q),+),
+                $e => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $($p:ident)::+ { $($q:ident),+ }, else $b:block) => {
+        {
+            match $x {
+                $($p)::+ { $($q),+ } => ($($q),+),
+                _ => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $($p:ident)::+ { $($q:ident),+ }) => {
+        {
+            match $x {
+                $($p)::+ { $($q),+ } => ($($q),+),
+                _ => panic!("Unexpected value found inside '{}'", stringify!($x)),
+            }
+        }
+    };
+
     ($x:expr, if $i:path, else |$e:ident| $b:block) => {
         {
             match $x {
                 $i(q) => q,
-                $e @ _ => $b,
+                $e => $b,
             }
         }
     };
@@ -179,6 +386,33 @@ macro_rules! inner {
         }
     };
 
+    ($x:expr, if $($i:path)|+, else |$e:ident| $b:block) => {
+        {
+            match $x {
+                $( $i(q) => q, )+
+                $e => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $($i:path)|+, else $b:block) => {
+        {
+            match $x {
+                $( $i(q) => q, )+
+                _ => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $($i:path)|+) => {
+        {
+            match $x {
+                $( $i(q) => q, )+
+                _ => panic!("Unexpected value found inside '{}'", stringify!($x)),
+            }
+        }
+    };
+
     ($x:expr, else |$e:ident| $b:block) => {
         {
             use $crate::IntoResult;
@@ -199,26 +433,112 @@ macro_rules! inner {
         }
     };
 
+    ($x:expr) => {
+        {
+            use $crate::IntoResult;
+            match $x.into_result() {
+                Ok(q) => q,
+                _ => panic!("Unexpected value found inside '{}'", stringify!($x)),
+            }
+        }
+    };
+}
+
+/// Like `inner!`, but borrows instead of moving - see module level
+/// documentation for details.
+#[macro_export]
+macro_rules! inner_ref {
+    ($x:expr, if $i:path, else |$e:ident| $b:block) => {
+        {
+            match $x {
+                $i(ref q) => q,
+                ref $e => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $i:path, else $b:block) => {
+        {
+            match $x {
+                $i(ref q) => q,
+                _ => $b,
+            }
+        }
+    };
+
     ($x:expr, if $i:path) => {
         {
             match $x {
-                $i(q) => q,
+                $i(ref q) => q,
                 _ => panic!("Unexpected value found inside '{}'", stringify!($x)),
             }
         }
     };
+}
 
-    ($x:expr) => {
+/// Like `inner!`, but mutably borrows instead of moving - see module
+/// level documentation for details.
+#[macro_export]
+macro_rules! inner_mut {
+    ($x:expr, if $i:path, else |$e:ident| $b:block) => {
         {
-            use $crate::IntoResult;
-            match $x.into_result() {
-                Ok(q) => q,
+            match $x {
+                $i(ref mut q) => q,
+                ref mut $e => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $i:path, else $b:block) => {
+        {
+            match $x {
+                $i(ref mut q) => q,
+                _ => $b,
+            }
+        }
+    };
+
+    ($x:expr, if $i:path) => {
+        {
+            match $x {
+                $i(ref mut q) => q,
                 _ => panic!("Unexpected value found inside '{}'", stringify!($x)),
             }
         }
     };
 }
 
+/// Like `inner!`, but evaluates to a `Result` instead of panicking or
+/// requiring an `else` clause - see module level documentation for
+/// details.
+#[macro_export]
+macro_rules! try_inner {
+    ($x:expr, if $i:path, err $err:expr) => {
+        {
+            match $x {
+                $i(q) => Ok(q),
+                _ => Err($err),
+            }
+        }
+    };
+
+    ($x:expr, if $i:path) => {
+        {
+            match $x {
+                $i(q) => Ok(q),
+                other => Err(other),
+            }
+        }
+    };
+
+    ($x:expr) => {
+        {
+            use $crate::IntoResult;
+            $x.into_result()
+        }
+    };
+}
+
 #[test]
 fn simple_opt() {
     assert_eq!(inner!(Some(7)), 7);
@@ -297,3 +617,186 @@ fn own_enum() {
 
 }
 
+#[test]
+fn tuple_variant() {
+    enum Shape {
+        Rect(u32, u32),
+        _Circle(u32),
+    }
+    let z = Shape::Rect(3, 4);
+    let (w, h) = inner!(z, if Shape::Rect(w, h));
+    assert_eq!(w * h, 12);
+}
+
+#[test]
+fn tuple_variant_else() {
+    #[allow(dead_code)]
+    enum Shape {
+        Rect(u32, u32),
+        Circle(u32),
+    }
+    let z = Shape::Circle(5);
+    let (w, h) = inner!(z, if Shape::Rect(w, h), else { (0, 0) });
+    assert_eq!((w, h), (0, 0));
+}
+
+#[test]
+fn struct_variant() {
+    enum Msg {
+        Move { x: i32, y: i32 },
+        _Quit,
+    }
+    let z = Msg::Move { x: 1, y: 2 };
+    let (x, y) = inner!(z, if Msg::Move { x, y });
+    assert_eq!((x, y), (1, 2));
+}
+
+#[test]
+fn struct_variant_else() {
+    #[allow(dead_code)]
+    enum Msg {
+        Move { x: i32, y: i32 },
+        Quit,
+    }
+    let z = Msg::Quit;
+    let (x, y) = inner!(z, if Msg::Move { x, y }, else |e| {
+        assert!(matches!(e, Msg::Quit));
+        (0, 0)
+    });
+    assert_eq!((x, y), (0, 0));
+}
+
+#[test]
+fn alternation() {
+    #[allow(dead_code)]
+    enum Fruit {
+        Apple(i32),
+        Pear(i32),
+        Rotten,
+    }
+    let z = Fruit::Pear(7);
+    let y = inner!(z, if Fruit::Apple | Fruit::Pear, else { 0 });
+    assert_eq!(y, 7);
+}
+
+#[test]
+fn alternation_else() {
+    #[allow(dead_code)]
+    enum Fruit {
+        Apple(i32),
+        Pear(i32),
+        Rotten,
+    }
+    let z = Fruit::Rotten;
+    let y = inner!(z, if Fruit::Apple | Fruit::Pear, else |e| {
+        assert!(matches!(e, Fruit::Rotten));
+        0
+    });
+    assert_eq!(y, 0);
+}
+
+#[test]
+fn ref_variant() {
+    enum Fruit {
+        Apple(i32),
+        _Orange(i16),
+    }
+    let z = Fruit::Apple(15);
+    assert_eq!(*inner_ref!(z, if Fruit::Apple), 15);
+    assert_eq!(15, inner!(z, if Fruit::Apple));
+}
+
+#[test]
+fn ref_variant_else() {
+    #[allow(dead_code)]
+    enum Fruit {
+        Apple(i32),
+        Orange(i16),
+    }
+    let z = Fruit::Orange(15);
+    let y = inner_ref!(z, if Fruit::Apple, else |e| {
+        assert!(matches!(e, Fruit::Orange(15)));
+        &0
+    });
+    assert_eq!(*y, 0);
+    // `z` was only borrowed by the mismatch arm, so it's still usable here.
+    assert!(matches!(z, Fruit::Orange(15)));
+}
+
+#[test]
+fn mut_variant() {
+    enum Fruit {
+        Apple(i32),
+        _Orange(i16),
+    }
+    let mut z = Fruit::Apple(15);
+    *inner_mut!(z, if Fruit::Apple) += 1;
+    assert_eq!(16, inner!(z, if Fruit::Apple));
+}
+
+#[test]
+fn mut_variant_else() {
+    #[allow(dead_code)]
+    enum Fruit {
+        Apple(i32),
+        Orange(i16),
+    }
+    let mut fallback = 0;
+    let mut z = Fruit::Orange(15);
+    let y = inner_mut!(z, if Fruit::Apple, else |e| {
+        assert!(matches!(e, Fruit::Orange(15)));
+        &mut fallback
+    });
+    *y += 1;
+    // `z` was only borrowed by the mismatch arm, so it's still usable here.
+    assert!(matches!(z, Fruit::Orange(15)));
+    assert_eq!(fallback, 1);
+}
+
+#[test]
+fn try_inner_plain() {
+    fn double_it() -> Result<i32, ()> {
+        let x = Some(3);
+        let v = try_inner!(x)?;
+        Ok(v * 2)
+    }
+    assert_eq!(double_it(), Ok(6));
+
+    fn fails() -> Result<i32, ()> {
+        let x: Option<i32> = None;
+        let v = try_inner!(x)?;
+        Ok(v * 2)
+    }
+    assert_eq!(fails(), Err(()));
+}
+
+#[test]
+fn try_inner_if() {
+    #[derive(Debug, PartialEq)]
+    enum Fruit {
+        Apple(i32),
+        Orange(i16),
+    }
+    let z = Fruit::Apple(15);
+    assert_eq!(try_inner!(z, if Fruit::Apple), Ok(15));
+
+    let z = Fruit::Orange(15);
+    assert!(try_inner!(z, if Fruit::Apple).is_err());
+}
+
+#[test]
+fn try_inner_err() {
+    #[derive(Debug, PartialEq)]
+    enum MyError {
+        Wrong,
+    }
+    #[allow(dead_code)]
+    enum Fruit {
+        Apple(i32),
+        Orange(i16),
+    }
+    let z = Fruit::Orange(15);
+    let y: Result<i32, MyError> = try_inner!(z, if Fruit::Apple, err MyError::Wrong);
+    assert_eq!(y, Err(MyError::Wrong));
+}
+